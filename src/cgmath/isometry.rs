@@ -0,0 +1,183 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use point::{Point, Point2, Point3};
+use rotation::{Rotation2, Rotation3, Rot2, Rot3, ToRot2, ToRot3};
+use vector::{Vector, Vec2, Vec3};
+
+/// A two-dimensional isometry: a rigid-body transform combining a rotation
+/// with a translation.
+#[deriving(Eq, Clone)]
+pub struct Iso2<S> {
+    rot: Rot2<S>,
+    trans: Vec2<S>,
+}
+
+impl<S: Float> Iso2<S> {
+    /// Construct a new isometry from a rotation and a translation.
+    pub fn new<R: ToRot2<S>>(rot: &R, trans: Vec2<S>) -> Iso2<S> {
+        Iso2 { rot: rot.to_rot2(), trans: trans }
+    }
+
+    /// The rotational part of the isometry.
+    #[inline]
+    pub fn rotation<'a>(&'a self) -> &'a Rot2<S> { &'a self.rot }
+
+    /// The translational part of the isometry.
+    #[inline]
+    pub fn translation<'a>(&'a self) -> &'a Vec2<S> { &'a self.trans }
+
+    #[inline]
+    pub fn rotate_point(&self, point: &Point2<S>) -> Point2<S> {
+        self.rot.rotate_point2(point).add_v(&self.trans)
+    }
+
+    #[inline]
+    pub fn rotate_vec(&self, vec: &Vec2<S>) -> Vec2<S> {
+        self.rot.rotate_vec2(vec)
+    }
+
+    /// Combine `self` with `other`, such that `self` is applied first and
+    /// `other` second.
+    pub fn concat(&self, other: &Iso2<S>) -> Iso2<S> {
+        Iso2 {
+            rot: other.rot.concat(&self.rot),
+            trans: other.rot.rotate_vec2(&self.trans).add_v(&other.trans),
+        }
+    }
+
+    #[inline]
+    pub fn concat_self(&mut self, other: &Iso2<S>) {
+        *self = self.concat(other);
+    }
+
+    /// The isometry that undoes `self`.
+    pub fn invert(&self) -> Iso2<S> {
+        let inv_rot = self.rot.invert();
+        let inv_trans = -inv_rot.rotate_vec2(&self.trans);
+        Iso2 { rot: inv_rot, trans: inv_trans }
+    }
+
+    #[inline]
+    pub fn invert_self(&mut self) {
+        *self = self.invert();
+    }
+}
+
+#[cfg(test)]
+mod iso2_tests {
+    use super::Iso2;
+    use angle::Rad;
+    use rotation::Rot2;
+    use vector::{Vector, Vec2};
+
+    #[test]
+    fn concat_invert_is_identity() {
+        let rot: Rot2<f64> = Rot2::from_angle(Rad { s: 0.7 });
+        let iso = Iso2::new(&rot, Vec2::new(1.0f64, 2.0));
+        let identity = iso.concat(&iso.invert());
+
+        let p = Vec2::new(4.0f64, 5.0);
+        let rotated = identity.rotate_vec(&p);
+        assert!((rotated.x - p.x).abs() < 1.0e-10);
+        assert!((rotated.y - p.y).abs() < 1.0e-10);
+
+        let trans = identity.translation();
+        assert!(trans.x.abs() < 1.0e-10 && trans.y.abs() < 1.0e-10);
+    }
+}
+
+/// A three-dimensional isometry: a rigid-body transform combining a rotation
+/// with a translation.
+#[deriving(Eq, Clone)]
+pub struct Iso3<S> {
+    rot: Rot3<S>,
+    trans: Vec3<S>,
+}
+
+impl<S: Float> Iso3<S> {
+    /// Construct a new isometry from a rotation and a translation.
+    pub fn new<R: ToRot3<S>>(rot: &R, trans: Vec3<S>) -> Iso3<S> {
+        Iso3 { rot: rot.to_rot3(), trans: trans }
+    }
+
+    /// The rotational part of the isometry.
+    #[inline]
+    pub fn rotation<'a>(&'a self) -> &'a Rot3<S> { &'a self.rot }
+
+    /// The translational part of the isometry.
+    #[inline]
+    pub fn translation<'a>(&'a self) -> &'a Vec3<S> { &'a self.trans }
+
+    #[inline]
+    pub fn rotate_point(&self, point: &Point3<S>) -> Point3<S> {
+        self.rot.rotate_point3(point).add_v(&self.trans)
+    }
+
+    #[inline]
+    pub fn rotate_vec(&self, vec: &Vec3<S>) -> Vec3<S> {
+        self.rot.rotate_vec3(vec)
+    }
+
+    /// Combine `self` with `other`, such that `self` is applied first and
+    /// `other` second.
+    pub fn concat(&self, other: &Iso3<S>) -> Iso3<S> {
+        Iso3 {
+            rot: other.rot.concat(&self.rot),
+            trans: other.rot.rotate_vec3(&self.trans).add_v(&other.trans),
+        }
+    }
+
+    #[inline]
+    pub fn concat_self(&mut self, other: &Iso3<S>) {
+        *self = self.concat(other);
+    }
+
+    /// The isometry that undoes `self`.
+    pub fn invert(&self) -> Iso3<S> {
+        let inv_rot = self.rot.invert();
+        let inv_trans = -inv_rot.rotate_vec3(&self.trans);
+        Iso3 { rot: inv_rot, trans: inv_trans }
+    }
+
+    #[inline]
+    pub fn invert_self(&mut self) {
+        *self = self.invert();
+    }
+}
+
+#[cfg(test)]
+mod iso3_tests {
+    use super::Iso3;
+    use angle::Rad;
+    use rotation::Rot3;
+    use vector::{Vector, Vec3};
+
+    #[test]
+    fn concat_invert_is_identity() {
+        let rot: Rot3<f64> = Rot3::from_angle_z(Rad { s: 0.7 });
+        let iso = Iso3::new(&rot, Vec3::new(1.0f64, 2.0, 3.0));
+        let identity = iso.concat(&iso.invert());
+
+        let p = Vec3::new(4.0f64, 5.0, 6.0);
+        let rotated = identity.rotate_vec(&p);
+        assert!((rotated.x - p.x).abs() < 1.0e-10);
+        assert!((rotated.y - p.y).abs() < 1.0e-10);
+        assert!((rotated.z - p.z).abs() < 1.0e-10);
+
+        let trans = identity.translation();
+        assert!(trans.x.abs() < 1.0e-10 && trans.y.abs() < 1.0e-10 && trans.z.abs() < 1.0e-10);
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright 2013 The CGMath Developers. For a full listing of the authors,
+// refer to the AUTHORS file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Computer graphics math.
+
+pub use angle::{Angle, Rad, acos, atan2};
+pub use isometry::{Iso2, Iso3};
+pub use matrix::{Matrix, Mat2, ToMat2, Mat3, ToMat3};
+pub use point::{Point, Point2, Point3};
+pub use quaternion::{Quat, ToQuat};
+pub use ray::{Ray2, Ray3};
+pub use rotation::{Rotation2, Rotation3, Rot2, Rot3, ToRot2, ToRot3};
+pub use vector::{Vector, Vec2, Vec3};
+
+mod angle;
+mod isometry;
+mod matrix;
+mod point;
+mod quaternion;
+mod ray;
+mod rotation;
+mod vector;
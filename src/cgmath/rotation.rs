@@ -13,7 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use angle::Angle;
+use std::num::One;
+use std::rand::{Rand, Rng};
+
+use angle::{Angle, Rad, acos, atan2};
 use matrix::Matrix;
 use matrix::{Mat2, ToMat2};
 use matrix::{Mat3, ToMat3};
@@ -39,6 +42,26 @@ pub trait Rotation2
     fn concat_self(&mut self, other: &Self);
     fn invert(&self) -> Self;
     fn invert_self(&mut self);
+
+    /// Append `delta` to `self`, as if `delta` were applied after `self`
+    /// in the world (extrinsic) frame.
+    #[inline]
+    fn append_rotation(&mut self, delta: &Self) { *self = self.append_rotation_cpy(delta); }
+
+    /// Prepend `delta` to `self`, as if `delta` were applied before `self`
+    /// in the local (intrinsic) frame.
+    #[inline]
+    fn prepend_rotation(&mut self, delta: &Self) { *self = self.prepend_rotation_cpy(delta); }
+
+    /// Return the result of appending `delta` to `self`, without modifying
+    /// `self`. See `append_rotation`.
+    #[inline]
+    fn append_rotation_cpy(&self, delta: &Self) -> Self { delta.concat(self) }
+
+    /// Return the result of prepending `delta` to `self`, without
+    /// modifying `self`. See `prepend_rotation`.
+    #[inline]
+    fn prepend_rotation_cpy(&self, delta: &Self) -> Self { self.concat(delta) }
 }
 
 /// A three-dimensional rotation
@@ -59,6 +82,64 @@ pub trait Rotation3
     fn concat_self(&mut self, other: &Self);
     fn invert(&self) -> Self;
     fn invert_self(&mut self);
+
+    /// Build a rotation around an arbitrary axis.
+    fn from_axis_angle<A: Angle<S>>(axis: &Vec3<S>, angle: A) -> Self;
+
+    /// Build a rotation from a set of euler angles.
+    fn from_euler<A: Angle<S>>(x: A, y: A, z: A) -> Self;
+
+    /// Append `delta` to `self`, as if `delta` were applied after `self`
+    /// in the world (extrinsic) frame.
+    #[inline]
+    fn append_rotation(&mut self, delta: &Self) { *self = self.append_rotation_cpy(delta); }
+
+    /// Prepend `delta` to `self`, as if `delta` were applied before `self`
+    /// in the local (intrinsic) frame.
+    #[inline]
+    fn prepend_rotation(&mut self, delta: &Self) { *self = self.prepend_rotation_cpy(delta); }
+
+    /// Return the result of appending `delta` to `self`, without modifying
+    /// `self`. See `append_rotation`.
+    #[inline]
+    fn append_rotation_cpy(&self, delta: &Self) -> Self { delta.concat(self) }
+
+    /// Return the result of prepending `delta` to `self`, without
+    /// modifying `self`. See `prepend_rotation`.
+    #[inline]
+    fn prepend_rotation_cpy(&self, delta: &Self) -> Self { self.concat(delta) }
+
+    /// Append a rotation around an arbitrary axis to `self`, without
+    /// having to build the delta rotation by hand.
+    #[inline]
+    fn append_rotation_axis_angle<A: Angle<S>>(&mut self, axis: &Vec3<S>, angle: A) {
+        let delta: Self = Rotation3::from_axis_angle(axis, angle);
+        self.append_rotation(&delta);
+    }
+
+    /// Prepend a rotation around an arbitrary axis to `self`, without
+    /// having to build the delta rotation by hand.
+    #[inline]
+    fn prepend_rotation_axis_angle<A: Angle<S>>(&mut self, axis: &Vec3<S>, angle: A) {
+        let delta: Self = Rotation3::from_axis_angle(axis, angle);
+        self.prepend_rotation(&delta);
+    }
+
+    /// Append a rotation built from a set of euler angles to `self`,
+    /// without having to build the delta rotation by hand.
+    #[inline]
+    fn append_rotation_euler<A: Angle<S>>(&mut self, x: A, y: A, z: A) {
+        let delta: Self = Rotation3::from_euler(x, y, z);
+        self.append_rotation(&delta);
+    }
+
+    /// Prepend a rotation built from a set of euler angles to `self`,
+    /// without having to build the delta rotation by hand.
+    #[inline]
+    fn prepend_rotation_euler<A: Angle<S>>(&mut self, x: A, y: A, z: A) {
+        let delta: Self = Rotation3::from_euler(x, y, z);
+        self.prepend_rotation(&delta);
+    }
 }
 
 /// A two-dimensional rotation matrix.
@@ -75,6 +156,21 @@ pub struct Rot2<S> {
 impl<S: Float> Rot2<S> {
     #[inline]
     pub fn as_mat2<'a>(&'a self) -> &'a Mat2<S> { &'a self.mat }
+
+    /// Create a rotation matrix from a rotation angle.
+    ///
+    /// Takes any `Angle<S>`, not just `Rad<S>`, the same as
+    /// `Rot3::from_angle_x`/`_y`/`_z` do by deferring to the matching
+    /// generic `Mat3::from_angle_x` et al. `Mat2::from_angle` follows that
+    /// same convention.
+    pub fn from_angle<A: Angle<S>>(theta: A) -> Rot2<S> {
+        Rot2 { mat: Mat2::from_angle(theta) }
+    }
+
+    /// Recover the signed rotation angle from the matrix.
+    pub fn rotation(&self) -> Rad<S> {
+        atan2(-self.mat.y.x, self.mat.x.x)
+    }
 }
 
 pub trait ToRot2<S: Float> {
@@ -93,13 +189,18 @@ impl<S: Float> ToMat2<S> for Rot2<S> {
 
 impl<S: Float> Rotation2<S> for Rot2<S> {
     #[inline]
-    fn rotate_point2(&self, _point: &Point2<S>) -> Point2<S> { fail!("Not yet implemented") }
+    fn rotate_point2(&self, point: &Point2<S>) -> Point2<S> {
+        let v = self.rotate_vec2(&Vec2::new(point.x, point.y));
+        Point2::new(v.x, v.y)
+    }
 
     #[inline]
     fn rotate_vec2(&self, vec: &Vec2<S>) -> Vec2<S> { self.mat.mul_v(vec) }
 
     #[inline]
-    fn rotate_ray2(&self, _ray: &Ray2<S>) -> Ray2<S> { fail!("Not yet implemented") }
+    fn rotate_ray2(&self, ray: &Ray2<S>) -> Ray2<S> {
+        Ray2::new(self.rotate_point2(&ray.origin), self.rotate_vec2(&ray.direction))
+    }
 
     #[inline]
     fn concat(&self, other: &Rot2<S>) -> Rot2<S> { Rot2 { mat: self.mat.mul_m(&other.mat) } }
@@ -107,15 +208,13 @@ impl<S: Float> Rotation2<S> for Rot2<S> {
     #[inline]
     fn concat_self(&mut self, other: &Rot2<S>) { self.mat.mul_self_m(&other.mat); }
 
-    // TODO: we know the matrix is orthogonal, so this could be re-written
-    // to be faster
+    // The matrix is guaranteed to be orthogonal, so the inverse is just
+    // the transpose.
     #[inline]
-    fn invert(&self) -> Rot2<S> { Rot2 { mat: self.mat.invert().unwrap() } }
+    fn invert(&self) -> Rot2<S> { Rot2 { mat: self.mat.transpose() } }
 
-    // TODO: we know the matrix is orthogonal, so this could be re-written
-    // to be faster
     #[inline]
-    fn invert_self(&mut self) { self.mat.invert_self(); }
+    fn invert_self(&mut self) { self.mat.transpose_self(); }
 }
 
 impl<S: Float> ApproxEq<S> for Rot2<S> {
@@ -186,6 +285,56 @@ impl<S: Float> Rot3<S> {
 
     #[inline]
     pub fn as_mat3<'a>(&'a self) -> &'a Mat3<S> { &'a self.mat }
+
+    /// Recover the axis and angle of rotation from the matrix.
+    ///
+    /// Near a rotation of zero the axis is indeterminate, and an arbitrary
+    /// unit axis is returned instead. Near a rotation of `pi` the
+    /// off-diagonal differences used in the general case vanish, so the
+    /// axis is instead recovered from the diagonal of the matrix.
+    pub fn to_axis_angle(&self) -> (Vec3<S>, Rad<S>) {
+        let m = &self.mat;
+        let one: S = One::one();
+        let two = one + one;
+        let four = two + two;
+
+        // Guard against fp drift pushing the argument outside `acos`'s
+        // domain.
+        let mut cos_angle = (m.trace() - one) / two;
+        if cos_angle > one { cos_angle = one; }
+        if cos_angle < -one { cos_angle = -one; }
+        let angle: Rad<S> = acos(cos_angle);
+
+        // Close to either end of `[0, pi]` the general-case axis below has
+        // magnitude ~2*sin(angle), which is only safe to normalize outside
+        // a band of width ~sqrt(epsilon) around the endpoints.
+        let tolerance: S = Float::epsilon().sqrt();
+
+        if angle.s < tolerance {
+            (Vec3::unit_x(), angle)
+        } else if Float::pi::<S>() - angle.s < tolerance {
+            // The off-diagonal differences used in the general case vanish
+            // here, so recover the axis from the diagonal instead. Pivot on
+            // the largest diagonal entry (guaranteed >= -1/3 since the axis
+            // is a unit vector) to keep the square root well-conditioned,
+            // and recover the other components' signs from the off-diagonal
+            // sums, which each equal `4 * axis_i * axis_j` at angle `pi`.
+            let axis = if m.x.x >= m.y.y && m.x.x >= m.z.z {
+                let x = ((m.x.x + one) / two).sqrt();
+                Vec3::new(x, (m.x.y + m.y.x) / (four * x), (m.x.z + m.z.x) / (four * x))
+            } else if m.y.y >= m.z.z {
+                let y = ((m.y.y + one) / two).sqrt();
+                Vec3::new((m.x.y + m.y.x) / (four * y), y, (m.y.z + m.z.y) / (four * y))
+            } else {
+                let z = ((m.z.z + one) / two).sqrt();
+                Vec3::new((m.x.z + m.z.x) / (four * z), (m.y.z + m.z.y) / (four * z), z)
+            };
+            (axis.normalize(), angle)
+        } else {
+            let axis = Vec3::new(m.y.z - m.z.y, m.z.x - m.x.z, m.x.y - m.y.x);
+            (axis.normalize(), angle)
+        }
+    }
 }
 
 pub trait ToRot3<S: Float> {
@@ -209,13 +358,18 @@ impl<S: Float> ToQuat<S> for Rot3<S> {
 
 impl<S: Float> Rotation3<S> for Rot3<S> {
     #[inline]
-    fn rotate_point3(&self, _point: &Point3<S>) -> Point3<S> { fail!("Not yet implemented") }
+    fn rotate_point3(&self, point: &Point3<S>) -> Point3<S> {
+        let v = self.rotate_vec3(&Vec3::new(point.x, point.y, point.z));
+        Point3::new(v.x, v.y, v.z)
+    }
 
     #[inline]
     fn rotate_vec3(&self, vec: &Vec3<S>) -> Vec3<S> { self.mat.mul_v(vec) }
 
     #[inline]
-    fn rotate_ray3(&self, _ray: &Ray3<S>) -> Ray3<S> { fail!("Not yet implemented") }
+    fn rotate_ray3(&self, ray: &Ray3<S>) -> Ray3<S> {
+        Ray3::new(self.rotate_point3(&ray.origin), self.rotate_vec3(&ray.direction))
+    }
 
     #[inline]
     fn concat(&self, other: &Rot3<S>) -> Rot3<S> { Rot3 { mat: self.mat.mul_m(&other.mat) } }
@@ -223,15 +377,23 @@ impl<S: Float> Rotation3<S> for Rot3<S> {
     #[inline]
     fn concat_self(&mut self, other: &Rot3<S>) { self.mat.mul_self_m(&other.mat); }
 
-    // TODO: we know the matrix is orthogonal, so this could be re-written
-    // to be faster
+    // The matrix is guaranteed to be orthogonal, so the inverse is just
+    // the transpose.
+    #[inline]
+    fn invert(&self) -> Rot3<S> { Rot3 { mat: self.mat.transpose() } }
+
     #[inline]
-    fn invert(&self) -> Rot3<S> { Rot3 { mat: self.mat.invert().unwrap() } }
+    fn invert_self(&mut self) { self.mat.transpose_self(); }
 
-    // TODO: we know the matrix is orthogonal, so this could be re-written
-    // to be faster
     #[inline]
-    fn invert_self(&mut self) { self.mat.invert_self(); }
+    fn from_axis_angle<A: Angle<S>>(axis: &Vec3<S>, angle: A) -> Rot3<S> {
+        Rot3 { mat: Mat3::from_axis_angle(axis, angle) }
+    }
+
+    #[inline]
+    fn from_euler<A: Angle<S>>(x: A, y: A, z: A) -> Rot3<S> {
+        Rot3 { mat: Mat3::from_euler(x, y, z) }
+    }
 }
 
 impl<S: Float> ApproxEq<S> for Rot3<S> {
@@ -266,13 +428,18 @@ impl<S: Float> ToQuat<S> for Quat<S> {
 
 impl<S: Float> Rotation3<S> for Quat<S> {
     #[inline]
-    fn rotate_point3(&self, _point: &Point3<S>) -> Point3<S> { fail!("Not yet implemented") }
+    fn rotate_point3(&self, point: &Point3<S>) -> Point3<S> {
+        let v = self.rotate_vec3(&Vec3::new(point.x, point.y, point.z));
+        Point3::new(v.x, v.y, v.z)
+    }
 
     #[inline]
     fn rotate_vec3(&self, vec: &Vec3<S>) -> Vec3<S> { self.mul_v(vec) }
 
     #[inline]
-    fn rotate_ray3(&self, _ray: &Ray3<S>) -> Ray3<S> { fail!("Not yet implemented") }
+    fn rotate_ray3(&self, ray: &Ray3<S>) -> Ray3<S> {
+        Ray3::new(self.rotate_point3(&ray.origin), self.rotate_vec3(&ray.direction))
+    }
 
     #[inline]
     fn concat(&self, other: &Quat<S>) -> Quat<S> { self.mul_q(other) }
@@ -285,4 +452,104 @@ impl<S: Float> Rotation3<S> for Quat<S> {
 
     #[inline]
     fn invert_self(&mut self) { *self = self.invert() }
+
+    #[inline]
+    fn from_axis_angle<A: Angle<S>>(axis: &Vec3<S>, angle: A) -> Quat<S> {
+        Mat3::from_axis_angle(axis, angle).to_quat()
+    }
+
+    #[inline]
+    fn from_euler<A: Angle<S>>(x: A, y: A, z: A) -> Quat<S> {
+        Mat3::from_euler(x, y, z).to_quat()
+    }
+}
+
+// Random rotation generation
+
+impl<S: Float + Rand> Rand for Quat<S> {
+    /// Return a uniformly distributed random rotation, using Ken Shoemake's
+    /// method described in "Uniform Random Rotations" (Graphics Gems III).
+    fn rand<R: Rng>(rng: &mut R) -> Quat<S> {
+        let one: S = One::one();
+        let two = one + one;
+        let pi: S = Float::pi();
+
+        let u1: S = rng.gen();
+        let u2: S = rng.gen();
+        let u3: S = rng.gen();
+
+        let a = (one - u1).sqrt();
+        let b = u1.sqrt();
+        let theta2 = two * pi * u2;
+        let theta3 = two * pi * u3;
+
+        Quat::new(b * theta3.cos(), a * theta2.sin(), a * theta2.cos(), b * theta3.sin())
+    }
+}
+
+impl<S: Float + Rand> Rand for Rot3<S> {
+    /// Return a uniformly distributed random rotation, by sampling a
+    /// uniformly distributed random `Quat` and converting it to a `Rot3`.
+    fn rand<R: Rng>(rng: &mut R) -> Rot3<S> {
+        let q: Quat<S> = rng.gen();
+        q.to_rot3()
+    }
+}
+
+impl<S: Float + Rand> Rand for Rot2<S> {
+    /// Return a uniformly distributed random rotation, by sampling a
+    /// uniformly distributed random angle in `[0, 2*pi)`.
+    fn rand<R: Rng>(rng: &mut R) -> Rot2<S> {
+        let one: S = One::one();
+        let two = one + one;
+        let pi: S = Float::pi();
+        let theta: S = rng.gen::<S>() * two * pi;
+
+        Rot2::from_angle(Rad { s: theta })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rot3, Rotation3};
+    use angle::Rad;
+    use vector::{Vector, Vec3};
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1.0e-5, "{} !~= {}", a, b);
+    }
+
+    fn assert_axis_close(a: Vec3<f64>, b: Vec3<f64>) {
+        assert_close(a.x, b.x);
+        assert_close(a.y, b.y);
+        assert_close(a.z, b.z);
+    }
+
+    #[test]
+    fn to_axis_angle_round_trips_near_zero() {
+        let axis = Vec3::new(0.0f64, 1.0, 0.0);
+        let rot: Rot3<f64> = Rotation3::from_axis_angle(&axis, Rad { s: 1.0e-6 });
+        let (out_axis, out_angle) = rot.to_axis_angle();
+        assert_close(out_angle.s, 1.0e-6);
+        assert_axis_close(out_axis, axis);
+    }
+
+    #[test]
+    fn to_axis_angle_round_trips_near_pi() {
+        let axis = Vec3::new(1.0f64, -1.0, 0.0).normalize();
+        let angle: f64 = Float::pi() - 1.0e-6;
+        let rot: Rot3<f64> = Rotation3::from_axis_angle(&axis, Rad { s: angle });
+        let (out_axis, out_angle) = rot.to_axis_angle();
+        assert_close(out_angle.s, angle);
+        assert_axis_close(out_axis, axis);
+    }
+
+    #[test]
+    fn to_axis_angle_round_trips_generic_angle() {
+        let axis = Vec3::new(1.0f64, 2.0, 3.0).normalize();
+        let rot: Rot3<f64> = Rotation3::from_axis_angle(&axis, Rad { s: 1.2 });
+        let (out_axis, out_angle) = rot.to_axis_angle();
+        assert_close(out_angle.s, 1.2);
+        assert_axis_close(out_axis, axis);
+    }
 }